@@ -6,11 +6,73 @@ use tui::backend::CrosstermBackend;
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans};
-use tui::widgets::{Block, Borders, Paragraph, Wrap};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use tui::{Frame, Terminal};
 
+use std::fmt::Write as _;
 use std::io::Stdout;
 
+/// Fallback used whenever the user's configured `date_format` is not a valid
+/// strftime pattern, so a config typo can't crash rendering.
+const DEFAULT_DATE_FORMAT: &str = "%H:%M:%S ";
+
+/// Which color scheme the TUI renders with, picked from config and stored in
+/// [`ApplicationState`] so every draw function can pull colors from the same place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+/// Colors used throughout the TUI for a given [`Theme`], replacing what used to be
+/// literals scattered across the draw functions.
+struct Palette {
+    foreground: Color,
+    dim: Color,
+    own_message: Color,
+    message_colors: [Color; 4],
+    notification_user: Color,
+    notification_content: Color,
+    error_user: Color,
+    error_content: Color,
+    progress: Color,
+    url: Color,
+    command: Color,
+}
+
+impl Theme {
+    fn palette(self) -> Palette {
+        match self {
+            Theme::Dark => Palette {
+                foreground: Color::White,
+                dim: Color::DarkGray,
+                own_message: Color::Green,
+                message_colors: [Color::Blue, Color::Yellow, Color::Cyan, Color::Magenta],
+                notification_user: Color::Yellow,
+                notification_content: Color::LightYellow,
+                error_user: Color::Red,
+                error_content: Color::LightRed,
+                progress: Color::LightGreen,
+                url: Color::LightBlue,
+                command: Color::LightYellow,
+            },
+            Theme::Light => Palette {
+                foreground: Color::Black,
+                dim: Color::Gray,
+                own_message: Color::Green,
+                message_colors: [Color::Blue, Color::Red, Color::Cyan, Color::Magenta],
+                notification_user: Color::Blue,
+                notification_content: Color::Black,
+                error_user: Color::Red,
+                error_content: Color::Black,
+                progress: Color::Green,
+                url: Color::Blue,
+                command: Color::Magenta,
+            },
+        }
+    }
+}
+
 pub fn draw(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     state: &ApplicationState,
@@ -31,7 +93,7 @@ fn draw_messages_panel(
     state: &ApplicationState,
     chunk: Rect,
 ) {
-    const MESSAGE_COLORS: [Color; 4] = [Color::Blue, Color::Yellow, Color::Cyan, Color::Magenta];
+    let palette = state.theme().palette();
 
     let mut messages = state
         .messages()
@@ -39,41 +101,72 @@ fn draw_messages_panel(
         .rev()
         .map(|message| {
             let color = if let Some(id) = state.users_id().get(&message.user) {
-                MESSAGE_COLORS[id % MESSAGE_COLORS.len()]
+                palette.message_colors[id % palette.message_colors.len()]
+            } else {
+                palette.own_message
+            };
+            // `date_shown` lets users hide timestamps entirely; otherwise render them
+            // with the user's own strftime format instead of a fixed "%H:%M:%S ".
+            // No manual width budget is needed for long custom formats here: the
+            // messages panel is wrapped by `Wrap { trim: false }` below, which
+            // reflows on the actual rendered width of every `Span` (timestamp
+            // included), not a precomputed assumption of its length. The input
+            // panel's own wrapping (`draw_input_panel`) is unaffected too, since it
+            // only budgets width for the user's typed text, never a timestamp.
+            let date_prefix: Vec<Span> = if state.date_shown() {
+                // `date_format` is free-form user config; an invalid/incomplete
+                // strftime pattern makes chrono's `Display` impl return `Err`,
+                // which `ToString::to_string()` would turn into a panic. `write!`
+                // lets us fall back to the default format instead of crashing.
+                let mut date_text = String::new();
+                if write!(date_text, "{}", message.date.format(state.date_format())).is_err() {
+                    date_text.clear();
+                    let _ = write!(date_text, "{}", message.date.format(DEFAULT_DATE_FORMAT));
+                }
+                vec![Span::styled(date_text, Style::default().fg(palette.dim))]
             } else {
-                Color::Green //because is a message of the own user
+                vec![]
             };
-            let date = message.date.format("%H:%M:%S ").to_string();
+
             match &message.message_type {
-                MessageType::Connection => Spans::from(vec![
-                    Span::styled(date, Style::default().fg(Color::DarkGray)),
-                    Span::styled(&message.user, Style::default().fg(color)),
-                    Span::styled(" is online", Style::default().fg(color)),
-                ]),
-                MessageType::Disconnection => Spans::from(vec![
-                    Span::styled(date, Style::default().fg(Color::DarkGray)),
-                    Span::styled(&message.user, Style::default().fg(color)),
-                    Span::styled(" is offline", Style::default().fg(color)),
-                ]),
+                MessageType::Connection => {
+                    let mut ui_message = date_prefix.clone();
+                    ui_message.extend(vec![
+                        Span::styled(&message.user, Style::default().fg(color)),
+                        Span::styled(" is online", Style::default().fg(color)),
+                    ]);
+                    Spans::from(ui_message)
+                }
+                MessageType::Disconnection => {
+                    let mut ui_message = date_prefix.clone();
+                    ui_message.extend(vec![
+                        Span::styled(&message.user, Style::default().fg(color)),
+                        Span::styled(" is offline", Style::default().fg(color)),
+                    ]);
+                    Spans::from(ui_message)
+                }
                 MessageType::Content(content) => {
-                    let mut ui_message = vec![
-                        Span::styled(date, Style::default().fg(Color::DarkGray)),
+                    let mut ui_message = date_prefix.clone();
+                    ui_message.extend(vec![
                         Span::styled(&message.user, Style::default().fg(color)),
                         Span::styled(": ", Style::default().fg(color)),
-                    ];
-                    ui_message.extend(parse_content(content));
+                    ]);
+                    ui_message.extend(parse_content(content, state.user_name(), &palette));
                     Spans::from(ui_message)
                 }
                 MessageType::Termchat(content, msg_type) => {
                     let (user_color, content_color) = match msg_type {
-                        TermchatMessageType::Notification => (Color::Yellow, Color::LightYellow),
-                        TermchatMessageType::Error => (Color::Red, Color::LightRed),
+                        TermchatMessageType::Notification => {
+                            (palette.notification_user, palette.notification_content)
+                        }
+                        TermchatMessageType::Error => (palette.error_user, palette.error_content),
                     };
-                    Spans::from(vec![
-                        Span::styled(date, Style::default().fg(Color::DarkGray)),
+                    let mut ui_message = date_prefix.clone();
+                    ui_message.extend(vec![
                         Span::styled(&message.user, Style::default().fg(user_color)),
                         Span::styled(content, Style::default().fg(content_color)),
-                    ])
+                    ]);
+                    Spans::from(ui_message)
                 }
             }
         })
@@ -81,7 +174,7 @@ fn draw_messages_panel(
 
     // check if there is a file being sent and if so draw the progress bar
     if let Some(progress) = state.progress() {
-        add_progress_bar(&mut messages, chunk.width, progress);
+        add_progress_bar(&mut messages, chunk.width, progress, &palette);
     }
 
     let messages_panel = Paragraph::new(messages)
@@ -89,7 +182,7 @@ fn draw_messages_panel(
             "LAN Room",
             Style::default().add_modifier(Modifier::BOLD),
         )))
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(palette.foreground))
         .alignment(Alignment::Left)
         .scroll((state.scroll_messages_view() as u16, 0))
         .wrap(Wrap { trim: false });
@@ -97,9 +190,14 @@ fn draw_messages_panel(
     frame.render_widget(messages_panel, chunk);
 }
 
-fn add_progress_bar(messages: &mut Vec<Spans>, panel_width: u16, progress: (usize, usize)) {
+fn add_progress_bar(
+    messages: &mut Vec<Spans>,
+    panel_width: u16,
+    progress: (usize, usize),
+    palette: &Palette,
+) {
     let (current, max) = progress;
-    let color = Color::LightGreen;
+    let color = palette.progress;
 
     let width = panel_width - 20;
     let ui_step = width as f32 / max as f32;
@@ -116,29 +214,206 @@ fn add_progress_bar(messages: &mut Vec<Spans>, panel_width: u16, progress: (usiz
     messages.insert(0, Spans::from(ui_message));
 }
 
-fn parse_content(content: &str) -> Vec<Span> {
-    let color_command = |command| {
-        content
-            .splitn(2, command)
-            .enumerate()
-            .map(|(index, part)| {
-                // ?send
-                if index == 0 {
-                    Span::styled(command, Style::default().fg(Color::LightYellow))
-                } else {
-                    Span::raw(part)
-                }
-            })
-            .collect()
+const SEND_COMMAND: &str = "?send";
+
+/// A single tokenized piece of a message body, as produced by [`tokenize_content`].
+/// New kinds (channel/user mentions, emoji, ...) can be added here without touching
+/// the whitespace-grouping logic.
+#[derive(Debug, PartialEq)]
+enum Fragment {
+    Text(String),
+    Url(String),
+    Command(String),
+}
+
+fn parse_content(content: &str, local_user: &str, palette: &Palette) -> Vec<Span<'static>> {
+    tokenize_content(content)
+        .into_iter()
+        .flat_map(|fragment| match fragment {
+            Fragment::Command(command) => {
+                vec![Span::styled(command, Style::default().fg(palette.command))]
+            }
+            Fragment::Url(url) => vec![Span::styled(
+                url,
+                Style::default()
+                    .fg(palette.url)
+                    .add_modifier(Modifier::UNDERLINED),
+            )],
+            Fragment::Text(text) => highlight_mentions(text, local_user),
+        })
+        .collect()
+}
+
+// Splits `content` into whitespace and non-whitespace runs, classifies each
+// non-whitespace run as a URL, the `?send` command, or plain text, and coalesces
+// consecutive `Text` runs back together so styling stays minimal.
+fn tokenize_content(content: &str) -> Vec<Fragment> {
+    let mut fragments: Vec<Fragment> = Vec::new();
+
+    let mut start = 0;
+    let mut in_whitespace = None;
+    let mut push_run = |run: &str, fragments: &mut Vec<Fragment>| {
+        let fragment = if run.starts_with(char::is_whitespace) {
+            Fragment::Text(run.to_string())
+        } else if run.starts_with("http://") || run.starts_with("https://") {
+            Fragment::Url(run.to_string())
+        } else if run == SEND_COMMAND {
+            Fragment::Command(run.to_string())
+        } else {
+            Fragment::Text(run.to_string())
+        };
+
+        match (fragments.last_mut(), &fragment) {
+            (Some(Fragment::Text(prev)), Fragment::Text(current)) => prev.push_str(current),
+            _ => fragments.push(fragment),
+        }
     };
 
-    const SEND_COMMAND: &str = "?send";
+    for (i, c) in content.char_indices() {
+        let ws = c.is_whitespace();
+        match in_whitespace {
+            None => in_whitespace = Some(ws),
+            Some(prev) if prev != ws => {
+                push_run(&content[start..i], &mut fragments);
+                start = i;
+                in_whitespace = Some(ws);
+            }
+            _ => {}
+        }
+    }
+    if start < content.len() {
+        push_run(&content[start..], &mut fragments);
+    }
+
+    fragments
+}
+
+// Splits `text` around every whole-word occurrence of `local_user`, styling the
+// matched word so a mention of you stands out among the rest of the message.
+fn highlight_mentions(text: String, local_user: &str) -> Vec<Span<'static>> {
+    if local_user.is_empty() {
+        return vec![Span::raw(text)];
+    }
+
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mention_style = Style::default()
+        .add_modifier(Modifier::BOLD)
+        .add_modifier(Modifier::REVERSED);
+
+    let mut spans = Vec::new();
+    // Byte offset into `text` (not into a re-sliced `rest`) so the word-boundary
+    // checks below always see the real character preceding/following a candidate
+    // match, even across several rejected matches.
+    let mut search_from = 0;
+    let mut pending_from = 0;
+    let mut found_any = false;
+
+    while let Some(rel_start) = text[search_from..].find(local_user) {
+        let start = search_from + rel_start;
+        let end = start + local_user.len();
+
+        let before_ok = text[..start]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !is_word_char(c));
+        let after_ok = text[end..]
+            .chars()
+            .next()
+            .map_or(true, |c| !is_word_char(c));
 
-    if content.starts_with(SEND_COMMAND) {
-        color_command(SEND_COMMAND)
-    // other commands can be handled here the same way
-    } else {
-        vec![Span::raw(content)]
+        if before_ok && after_ok {
+            found_any = true;
+            if start > pending_from {
+                spans.push(Span::raw(text[pending_from..start].to_string()));
+            }
+            spans.push(Span::styled(text[start..end].to_string(), mention_style));
+            pending_from = end;
+            search_from = end;
+        } else {
+            // not a whole-word match: keep scanning past the first char of the
+            // match (may be multi-byte, so skip a full `char`, not a fixed byte)
+            search_from = start + text[start..].chars().next().map_or(1, |c| c.len_utf8());
+        }
+    }
+
+    if !found_any {
+        return vec![Span::raw(text)];
+    }
+
+    if pending_from < text.len() {
+        spans.push(Span::raw(text[pending_from..].to_string()));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod content_tests {
+    use super::*;
+
+    fn span_text(span: &Span) -> String {
+        span.content.to_string()
+    }
+
+    #[test]
+    fn mention_does_not_match_inside_a_longer_word() {
+        let spans = highlight_mentions("bobby said hi".to_string(), "bob");
+        assert_eq!(
+            spans.iter().map(span_text).collect::<Vec<_>>(),
+            vec!["bobby said hi"]
+        );
+    }
+
+    #[test]
+    fn mention_matches_at_start_and_end_of_text() {
+        let spans = highlight_mentions("bob and bob".to_string(), "bob");
+        assert_eq!(
+            spans.iter().map(span_text).collect::<Vec<_>>(),
+            vec!["bob", " and ", "bob"]
+        );
+    }
+
+    #[test]
+    fn mention_skips_a_full_multi_byte_char_on_a_rejected_match() {
+        // "étoile" inside "étoilerie" is not a whole-word match; this must not
+        // panic on the accented, multi-byte leading character.
+        let spans = highlight_mentions("étoilerie and étoile".to_string(), "étoile");
+        assert_eq!(
+            spans.iter().map(span_text).collect::<Vec<_>>(),
+            vec!["étoilerie and ", "étoile"]
+        );
+    }
+
+    #[test]
+    fn rejected_match_does_not_leak_into_a_later_word_boundary_check() {
+        // "aa" is the tail of the single word "xaaa", not a standalone mention:
+        // the boundary check after a rejected match must still see the real
+        // preceding character ('x'/'a'), not a byte offset into a truncated rest.
+        let spans = highlight_mentions("xaaa".to_string(), "aa");
+        assert_eq!(
+            spans.iter().map(span_text).collect::<Vec<_>>(),
+            vec!["xaaa"]
+        );
+    }
+
+    #[test]
+    fn tokenize_coalesces_text_around_url_and_command() {
+        let fragments = tokenize_content("hi check http://example.com then ?send ok");
+        assert_eq!(
+            fragments,
+            vec![
+                Fragment::Text("hi check ".to_string()),
+                Fragment::Url("http://example.com".to_string()),
+                Fragment::Text(" then ".to_string()),
+                Fragment::Command("?send".to_string()),
+                Fragment::Text(" ok".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_only_matches_the_command_exactly_not_as_a_prefix() {
+        let fragments = tokenize_content("?sendfile.txt");
+        assert_eq!(fragments, vec![Fragment::Text("?sendfile.txt".to_string())]);
     }
 }
 
@@ -147,10 +422,11 @@ fn draw_input_panel(
     state: &ApplicationState,
     chunk: Rect,
 ) {
+    let palette = state.theme().palette();
     let inner_width = (chunk.width - 2) as usize;
 
-    let input = state.input().iter().collect::<String>();
-    let input = split_each(input, inner_width)
+    let raw_input = state.input().iter().collect::<String>();
+    let input = split_each(raw_input.clone(), inner_width)
         .into_iter()
         .map(|line| Spans::from(vec![Span::raw(line)]))
         .collect::<Vec<_>>();
@@ -160,11 +436,192 @@ fn draw_input_panel(
             "Your message",
             Style::default().add_modifier(Modifier::BOLD),
         )))
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(palette.foreground))
         .alignment(Alignment::Left);
 
     frame.render_widget(input_panel, chunk);
 
     let input_cursor = state.ui_input_cursor(inner_width);
-    frame.set_cursor(chunk.x + 1 + input_cursor.0, chunk.y + 1 + input_cursor.1)
+    frame.set_cursor(chunk.x + 1 + input_cursor.0, chunk.y + 1 + input_cursor.1);
+
+    let candidates = completion_candidates(&raw_input);
+    if !candidates.is_empty() {
+        draw_completion_popup(
+            frame,
+            chunk,
+            &candidates,
+            state.completion_selected(),
+            &palette,
+        );
+    }
+}
+
+/// Prefix that marks the start of a command, e.g. `?send`.
+const COMMAND_PREFIX: char = '?';
+
+/// Commands the completion popup can suggest. New commands only need adding here.
+const COMMANDS: &[&str] = &[SEND_COMMAND];
+
+// Ranks `COMMANDS` against `input` with a SkimMatcherV2-style subsequence matcher:
+// every character of `input` must appear in order inside the candidate, consecutive
+// hits and matches right after a word boundary score higher, gaps are penalized.
+// Returns (candidate, score, matched char positions), best match first.
+fn completion_candidates(input: &str) -> Vec<(String, i64, Vec<usize>)> {
+    if !input.starts_with(COMMAND_PREFIX) || input.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+
+    let mut ranked = COMMANDS
+        .iter()
+        .filter_map(|&command| {
+            fuzzy_match(command, input)
+                .map(|(score, positions)| (command.to_string(), score, positions))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1));
+    ranked
+}
+
+fn fuzzy_match(candidate: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    let candidate_chars = candidate.chars().collect::<Vec<_>>();
+
+    let mut score = 0i64;
+    let mut consecutive = 0i64;
+    let mut candidate_idx = 0;
+    let mut matched_positions = Vec::new();
+
+    for qc in query.chars() {
+        let mut matched = false;
+        while candidate_idx < candidate_chars.len() {
+            let position = candidate_idx;
+            let cc = candidate_chars[position];
+            candidate_idx += 1;
+
+            if cc.to_ascii_lowercase() == qc.to_ascii_lowercase() {
+                consecutive += 1;
+                score += 10 + consecutive * 5;
+                let at_word_boundary =
+                    position == 0 || !candidate_chars[position - 1].is_alphanumeric();
+                if at_word_boundary {
+                    score += 10;
+                }
+                matched_positions.push(position);
+                matched = true;
+                break;
+            } else {
+                consecutive = 0;
+                score -= 1;
+            }
+        }
+        if !matched {
+            return None;
+        }
+    }
+
+    Some((score, matched_positions))
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use super::*;
+
+    #[test]
+    fn exact_prefix_outranks_a_scattered_subsequence() {
+        let (prefix_score, _) = fuzzy_match("send", "se").unwrap();
+        let (scattered_score, _) = fuzzy_match("stream_end", "se").unwrap();
+        assert!(prefix_score > scattered_score);
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(fuzzy_match("send", "es"), None);
+    }
+
+    #[test]
+    fn candidates_require_the_command_prefix() {
+        assert!(completion_candidates("send").is_empty());
+    }
+
+    #[test]
+    fn candidates_stop_once_the_command_token_is_complete() {
+        // a trailing space means the command is already typed out, nothing left to
+        // complete
+        assert!(completion_candidates("?send ").is_empty());
+    }
+}
+
+// Floating popup drawn over the top of the input chunk, listing fuzzy-ranked
+// command candidates with the matched characters highlighted and the current
+// selection reversed.
+fn draw_completion_popup(
+    frame: &mut Frame<CrosstermBackend<Stdout>>,
+    input_chunk: Rect,
+    candidates: &[(String, i64, Vec<usize>)],
+    selected: usize,
+    palette: &Palette,
+) {
+    const MAX_VISIBLE: usize = 5;
+
+    // `selected` can wrap past the candidate list (the caller increments it on
+    // every Tab press without clamping); keep it in range, then slide a
+    // `MAX_VISIBLE`-tall window so the selection is always inside `visible`
+    // instead of being silently clamped to whatever the first page shows.
+    let visible_len = candidates.len().min(MAX_VISIBLE);
+    let selected = selected % candidates.len();
+    let window_start = selected
+        .saturating_sub(visible_len - 1)
+        .min(candidates.len() - visible_len);
+    let visible = &candidates[window_start..window_start + visible_len];
+    let selected = selected - window_start;
+
+    let height = visible.len() as u16 + 2;
+    let width = visible
+        .iter()
+        .map(|(candidate, _, _)| candidate.len())
+        .max()
+        .unwrap_or(0) as u16
+        + 2;
+
+    let popup_area = Rect {
+        x: input_chunk.x + 1,
+        y: input_chunk.y.saturating_sub(height),
+        width: width.min(input_chunk.width.saturating_sub(2)),
+        height,
+    };
+
+    let match_style = Style::default()
+        .fg(palette.command)
+        .add_modifier(Modifier::BOLD);
+    let rest_style = Style::default().fg(palette.foreground);
+    let selected_modifier = Modifier::REVERSED;
+
+    let lines = visible
+        .iter()
+        .enumerate()
+        .map(|(i, (candidate, _, matched_positions))| {
+            let spans = candidate
+                .chars()
+                .enumerate()
+                .map(|(char_index, c)| {
+                    let mut style = if matched_positions.contains(&char_index) {
+                        match_style
+                    } else {
+                        rest_style
+                    };
+                    if i == selected {
+                        style = style.add_modifier(selected_modifier);
+                    }
+                    Span::styled(c.to_string(), style)
+                })
+                .collect::<Vec<_>>();
+            Spans::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    let popup = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(palette.foreground));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
 }